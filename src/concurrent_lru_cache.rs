@@ -0,0 +1,379 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+
+/// 增量层数达到该值时将链压实（flatten）为一个新的 `Base`，避免查找随提交次数
+/// 线性退化
+const MAX_LAYER_DEPTH: usize = 8;
+
+/// 写时复制的值存储。一次提交只记录自己的增量 `changes`，并通过 `parent`
+/// 链接上一版本——未被本次事务触碰的条目不会被拷贝，而是与旧快照
+/// 结构共享（压实时除外：届时新建一个 `HashMap`，键被克隆，值仍通过 `Arc`
+/// 共享）。`changes` 中的 `None` 表示该 key 相对 `parent` 被删除（墓碑）。
+enum Data<K, V> {
+    Base(HashMap<K, Arc<V>>),
+    Layer {
+        parent: Arc<Data<K, V>>,
+        changes: HashMap<K, Option<Arc<V>>>,
+        depth: usize,
+    },
+}
+
+impl<K: Eq + Hash, V> Data<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        match self {
+            Data::Base(values) => values.get(key).map(|val| val.as_ref()),
+            Data::Layer { parent, changes, .. } => match changes.get(key) {
+                Some(Some(val)) => Some(val.as_ref()),
+                Some(None) => None,
+                None => parent.get(key),
+            },
+        }
+    }
+
+    fn depth(&self) -> usize {
+        match self {
+            Data::Base(_) => 0,
+            Data::Layer { depth, .. } => *depth,
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> Data<K, V> {
+    /// 在当前版本之上叠加一层增量，层数达到 `MAX_LAYER_DEPTH` 时压实为新的 `Base`
+    fn push_layer(self: &Arc<Self>, changes: HashMap<K, Option<Arc<V>>>) -> Arc<Self> {
+        let depth = self.depth() + 1;
+        if depth >= MAX_LAYER_DEPTH {
+            Arc::new(Data::Base(self.flatten(&changes)))
+        } else {
+            Arc::new(Data::Layer {
+                parent: self.clone(),
+                changes,
+                depth,
+            })
+        }
+    }
+
+    fn flatten(&self, top_changes: &HashMap<K, Option<Arc<V>>>) -> HashMap<K, Arc<V>> {
+        let mut result = HashMap::new();
+        let mut seen = HashSet::new();
+        for (key, val) in top_changes {
+            seen.insert(key.clone());
+            if let Some(val) = val {
+                result.insert(key.clone(), val.clone());
+            }
+        }
+        self.collect_into(&mut result, &mut seen);
+        result
+    }
+
+    fn collect_into(&self, result: &mut HashMap<K, Arc<V>>, seen: &mut HashSet<K>) {
+        match self {
+            Data::Base(values) => {
+                for (key, val) in values {
+                    if seen.insert(key.clone()) {
+                        result.insert(key.clone(), val.clone());
+                    }
+                }
+            }
+            Data::Layer {
+                parent, changes, ..
+            } => {
+                for (key, val) in changes {
+                    if seen.insert(key.clone()) {
+                        if let Some(val) = val {
+                            result.insert(key.clone(), val.clone());
+                        }
+                    }
+                }
+                parent.collect_into(result, seen);
+            }
+        }
+    }
+}
+
+/// 一份不可变的缓存快照：`data` 与旧版本结构共享，`order` 记录从最近到
+/// 最久未写入的 key；读取（包括事务内的 `get`）不改变该顺序
+struct Inner<K, V> {
+    data: Arc<Data<K, V>>,
+    order: Vec<K>,
+}
+
+impl<K, V> Inner<K, V> {
+    fn empty() -> Self {
+        Self {
+            data: Arc::new(Data::Base(HashMap::new())),
+            order: Vec::new(),
+        }
+    }
+}
+
+/// 支持多线程并发读取的 LRU 缓存。
+///
+/// 采用写时复制（copy-on-write）策略：共享指针指向一份不可变快照，读者克隆一份
+/// `Arc` 后即可在不持有任何锁的情况下自由读取；写者基于当前快照构建新版本，
+/// 未改动条目的值与旧快照结构共享，但记录顺序的 `order` 在每次提交时整体
+/// 重建，所有 key 都会被克隆一次。提交时
+/// 原子地将共享指针替换为新版本；未显式 `commit` 的写事务也会在析构时发布，
+/// 因此 `write()` 返回的事务总是"发布而非回滚"。写者之间通过内部锁互斥，
+/// 同一时刻只有一个事务可以存在，避免并发提交互相覆盖。
+pub struct ConcurrentLruCache<K, V> {
+    current: RwLock<Arc<Inner<K, V>>>,
+    write_lock: Mutex<()>,
+    max_size: usize,
+}
+
+impl<K: Eq + Hash + Clone, V> ConcurrentLruCache<K, V> {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(Inner::empty())),
+            write_lock: Mutex::new(()),
+            max_size,
+        }
+    }
+
+    /// 获取一份一致的只读快照，快照在其生命周期内不受后续写入影响
+    pub fn read(&self) -> ReadGuard<K, V> {
+        let snapshot = self.current.read().expect("lock poisoned").clone();
+        ReadGuard { snapshot }
+    }
+
+    /// 开启一次写事务，变更在事务提交（或被析构）时才对新的读者可见。
+    /// 事务持有期间其它线程对 `write()` 的调用会阻塞，保证同一时刻只有
+    /// 一个写者在修改缓存。
+    pub fn write(&self) -> WriteTransaction<'_, K, V> {
+        let guard = self.write_lock.lock().expect("lock poisoned");
+        let base = self.current.read().expect("lock poisoned").clone();
+        WriteTransaction {
+            cache: self,
+            base,
+            changes: HashMap::new(),
+            touched: Vec::new(),
+            len_delta: 0,
+            committed: false,
+            _guard: guard,
+        }
+    }
+}
+
+/// 针对某一快照的只读视图，`get`/`peek` 均不产生任何提升访问顺序的副作用
+pub struct ReadGuard<K, V> {
+    snapshot: Arc<Inner<K, V>>,
+}
+
+impl<K: Eq + Hash, V> ReadGuard<K, V> {
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.snapshot.data.get(key)
+    }
+
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshot.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshot.order.is_empty()
+    }
+}
+
+/// 单个写者独占的事务：持有内部写锁，期间其它线程无法再开启写事务。
+/// 所有修改先记录在本地的 `changes`/`touched` 增量里，直到 `commit` 或
+/// 析构时才整体发布为一个与 `base` 结构共享的新版本。
+pub struct WriteTransaction<'a, K: Eq + Hash + Clone, V> {
+    cache: &'a ConcurrentLruCache<K, V>,
+    base: Arc<Inner<K, V>>,
+    changes: HashMap<K, Option<Arc<V>>>,
+    touched: Vec<K>,
+    len_delta: isize,
+    committed: bool,
+    _guard: MutexGuard<'a, ()>,
+}
+
+impl<'a, K: Eq + Hash + Clone, V> WriteTransaction<'a, K, V> {
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.changes.get(key) {
+            Some(Some(val)) => Some(val.as_ref()),
+            Some(None) => None,
+            None => self.base.data.get(key),
+        }
+    }
+
+    pub fn set(&mut self, key: K, val: V) {
+        if self.get(&key).is_none() {
+            self.len_delta += 1;
+        }
+        self.touched.retain(|existing| existing != &key);
+        self.touched.push(key.clone());
+        self.changes.insert(key, Some(Arc::new(val)));
+    }
+
+    pub fn len(&self) -> usize {
+        (self.base.order.len() as isize + self.len_delta).max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 立即将本次事务的更改发布给后续的读者
+    pub fn commit(mut self) {
+        self.publish();
+        self.committed = true;
+    }
+
+    fn publish(&mut self) {
+        let touched_set: HashSet<&K> = self.touched.iter().collect();
+        let mut new_order = Vec::with_capacity(self.touched.len() + self.base.order.len());
+
+        // 本次被写入的 key 按最近使用排在最前面
+        for key in self.touched.iter().rev() {
+            new_order.push(key.clone());
+        }
+        for key in &self.base.order {
+            if !touched_set.contains(key) && !matches!(self.changes.get(key), Some(None)) {
+                new_order.push(key.clone());
+            }
+        }
+
+        let mut changes = std::mem::take(&mut self.changes);
+        while new_order.len() > self.cache.max_size {
+            if let Some(evicted) = new_order.pop() {
+                changes.insert(evicted, None);
+            }
+        }
+
+        let inner = Inner {
+            data: self.base.data.push_layer(changes),
+            order: new_order,
+        };
+
+        let mut current = self.cache.current.write().expect("lock poisoned");
+        *current = Arc::new(inner);
+    }
+}
+
+impl<'a, K: Eq + Hash + Clone, V> Drop for WriteTransaction<'a, K, V> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.publish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn write_is_invisible_until_commit() {
+        let cache: ConcurrentLruCache<i32, i32> = ConcurrentLruCache::new(10);
+
+        let before = cache.read();
+        assert_eq!(before.get(&1), None);
+
+        let mut txn = cache.write();
+        txn.set(1, 100);
+        assert_eq!(before.get(&1), None); // snapshot held by `before` is unaffected
+        txn.commit();
+
+        let after = cache.read();
+        assert_eq!(after.get(&1), Some(&100));
+    }
+
+    #[test]
+    fn uncommitted_transaction_publishes_on_drop() {
+        let cache: ConcurrentLruCache<i32, i32> = ConcurrentLruCache::new(10);
+
+        {
+            let mut txn = cache.write();
+            txn.set(1, 1);
+        }
+
+        assert_eq!(cache.read().get(&1), Some(&1));
+    }
+
+    #[test]
+    fn evicts_lru_entry_past_capacity() {
+        let cache: ConcurrentLruCache<i32, i32> = ConcurrentLruCache::new(2);
+
+        let mut txn = cache.write();
+        txn.set(1, 1);
+        txn.set(2, 2);
+        txn.set(3, 3);
+        txn.commit();
+
+        let snapshot = cache.read();
+        assert_eq!(snapshot.get(&1), None);
+        assert_eq!(snapshot.get(&2), Some(&2));
+        assert_eq!(snapshot.get(&3), Some(&3));
+    }
+
+    #[test]
+    fn readers_observe_a_consistent_snapshot_while_writer_runs() {
+        let cache: Arc<ConcurrentLruCache<i32, i32>> = Arc::new(ConcurrentLruCache::new(100));
+        {
+            let mut txn = cache.write();
+            txn.set(0, 0);
+            txn.commit();
+        }
+
+        let reader_cache = cache.clone();
+        let reader = thread::spawn(move || {
+            let snapshot = reader_cache.read();
+            snapshot.get(&0).copied()
+        });
+
+        {
+            let mut txn = cache.write();
+            txn.set(1, 1);
+            txn.commit();
+        }
+
+        assert_eq!(reader.join().unwrap(), Some(0));
+    }
+
+    #[test]
+    fn concurrent_writers_are_serialized_and_both_commits_survive() {
+        let cache: Arc<ConcurrentLruCache<i32, i32>> = Arc::new(ConcurrentLruCache::new(10));
+
+        let writer_cache = cache.clone();
+        let writer = thread::spawn(move || {
+            let mut txn = writer_cache.write();
+            thread::sleep(Duration::from_millis(20)); // widen the window for a race
+            txn.set(1, 1);
+            txn.commit();
+        });
+
+        thread::sleep(Duration::from_millis(5)); // let the first writer grab the lock first
+        let mut txn = cache.write();
+        txn.set(2, 2);
+        txn.commit();
+
+        writer.join().unwrap();
+
+        let snapshot = cache.read();
+        assert_eq!(snapshot.get(&1), Some(&1));
+        assert_eq!(snapshot.get(&2), Some(&2));
+    }
+
+    #[test]
+    fn compacts_after_many_layers_without_losing_data() {
+        let cache: ConcurrentLruCache<i32, i32> = ConcurrentLruCache::new(100);
+
+        for i in 0..(MAX_LAYER_DEPTH as i32 * 3) {
+            let mut txn = cache.write();
+            txn.set(i, i);
+            txn.commit();
+        }
+
+        let snapshot = cache.read();
+        for i in 0..(MAX_LAYER_DEPTH as i32 * 3) {
+            assert_eq!(snapshot.get(&i), Some(&i));
+        }
+    }
+}
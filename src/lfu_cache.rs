@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::cell::RefCell;
+use std::hash::Hash;
+use std::rc::{Rc, Weak};
+
+struct Node<K, V> {
+    key: K,
+    val: V,
+    freq: usize,
+    list_prev: Option<Weak<RefCell<Node<K, V>>>>,
+    list_next: Option<Rc<RefCell<Node<K, V>>>>,
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, val: V) -> Self {
+        Self {
+            key,
+            val,
+            freq: 1,
+            list_prev: None,
+            list_next: None,
+        }
+    }
+}
+
+/// 按访问顺序排列同一频率下的节点，头部为最近访问
+struct FreqList<K, V> {
+    header: Option<Rc<RefCell<Node<K, V>>>>,
+    tail: Option<Rc<RefCell<Node<K, V>>>>,
+}
+
+impl<K, V> FreqList<K, V> {
+    fn new() -> Self {
+        Self {
+            header: None,
+            tail: None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.header.is_none()
+    }
+
+    fn push_front(&mut self, node: Rc<RefCell<Node<K, V>>>) {
+        node.borrow_mut().list_prev = None;
+        node.borrow_mut().list_next = self.header.clone();
+
+        if let Some(header) = self.header.clone() {
+            header.borrow_mut().list_prev = Some(Rc::downgrade(&node));
+        }
+        self.header = Some(node.clone());
+
+        if self.tail.is_none() {
+            self.tail = Some(node);
+        }
+    }
+
+    fn unlink(&mut self, node: &Rc<RefCell<Node<K, V>>>) {
+        let prev_node = node.borrow().list_prev.clone().and_then(|prev| prev.upgrade());
+        let next_node = node.borrow().list_next.clone();
+
+        if let Some(prev_node) = prev_node.as_ref() {
+            prev_node.borrow_mut().list_next = next_node.clone();
+        } else {
+            self.header = next_node.clone();
+        }
+
+        if let Some(next_node) = next_node.as_ref() {
+            next_node.borrow_mut().list_prev = prev_node.as_ref().map(Rc::downgrade);
+        } else {
+            self.tail = prev_node.clone();
+        }
+
+        node.borrow_mut().list_prev = None;
+        node.borrow_mut().list_next = None;
+    }
+
+    fn pop_back(&mut self) -> Option<Rc<RefCell<Node<K, V>>>> {
+        let tail = self.tail.clone()?;
+        self.unlink(&tail);
+        Some(tail)
+    }
+}
+
+/// 最不经常使用（LFU）淘汰策略的缓存，`get`/`set` 均为 O(1)。
+///
+/// 相同频率的条目之间按最近最少使用（LRU）打破平局：每个频率对应一个
+/// 按访问顺序排列的链表，`min_freq` 记录当前存在条目的最小频率，
+/// 淘汰时从该链表的尾部取出节点。
+pub struct LFUCache<K, V> {
+    max_size: usize,
+    total_size: usize,
+    min_freq: usize,
+    entries: HashMap<K, Rc<RefCell<Node<K, V>>>>,
+    freq_lists: HashMap<usize, FreqList<K, V>>,
+}
+
+impl<K: Eq + Hash + Clone, V> LFUCache<K, V> {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            total_size: 0,
+            min_freq: 0,
+            entries: HashMap::new(),
+            freq_lists: HashMap::new(),
+        }
+    }
+
+    pub fn get<T, F>(&mut self, key: T, then: F)
+    where
+        T: std::borrow::Borrow<K>,
+        F: Fn(&V),
+    {
+        if let Some(node) = self.entries.get(key.borrow()).cloned() {
+            self.touch(&node);
+            then(&node.borrow().val)
+        }
+    }
+
+    pub fn try_get<T: std::borrow::Borrow<K>>(&mut self, key: T) -> Option<V>
+    where
+        V: Clone,
+    {
+        if let Some(node) = self.entries.get(key.borrow()).cloned() {
+            self.touch(&node);
+            Some(node.borrow().val.clone())
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, key: K, val: V) {
+        if self.max_size == 0 {
+            return;
+        }
+
+        if let Some(node) = self.entries.get(&key).cloned() {
+            node.borrow_mut().val = val;
+            self.touch(&node);
+            return;
+        }
+
+        if self.total_size >= self.max_size {
+            self.evict();
+        }
+
+        let node = Rc::new(RefCell::new(Node::new(key.clone(), val)));
+        self.freq_lists
+            .entry(1)
+            .or_insert_with(FreqList::new)
+            .push_front(node.clone());
+        self.entries.insert(key, node);
+        self.min_freq = 1;
+        self.total_size += 1;
+    }
+
+    fn touch(&mut self, node: &Rc<RefCell<Node<K, V>>>) {
+        let freq = node.borrow().freq;
+
+        if let Some(list) = self.freq_lists.get_mut(&freq) {
+            list.unlink(node);
+            if freq == self.min_freq && list.is_empty() {
+                self.min_freq += 1;
+            }
+        }
+
+        let next_freq = freq + 1;
+        node.borrow_mut().freq = next_freq;
+        self.freq_lists
+            .entry(next_freq)
+            .or_insert_with(FreqList::new)
+            .push_front(node.clone());
+    }
+
+    fn evict(&mut self) {
+        if let Some(list) = self.freq_lists.get_mut(&self.min_freq) {
+            if let Some(node) = list.pop_back() {
+                let key = node.borrow().key.clone();
+                self.entries.remove(&key);
+                self.total_size -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_01() {
+        let mut cache: LFUCache<i32, i32> = LFUCache::new(2);
+
+        cache.set(1, 1);
+        cache.set(2, 2);
+        cache.get(1, |v| println!("get: {:?}", v)); // freq(1) = 2, freq(2) = 1
+
+        cache.set(3, 3); // evicts key 2 (min_freq bucket)
+        assert_eq!(cache.try_get(2), None);
+        assert_eq!(cache.try_get(1), Some(1));
+        assert_eq!(cache.try_get(3), Some(3));
+    }
+
+    #[test]
+    fn test_02_tie_breaks_by_recency() {
+        let mut cache: LFUCache<&str, i32> = LFUCache::new(2);
+
+        cache.set("a", 1);
+        cache.set("b", 2); // both at freq 1, "a" is the LRU of that bucket
+
+        cache.set("c", 3); // evicts "a"
+        assert_eq!(cache.try_get("a"), None);
+        assert_eq!(cache.try_get("b"), Some(2));
+        assert_eq!(cache.try_get("c"), Some(3));
+    }
+
+    struct DropCounter(Rc<RefCell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            *self.0.borrow_mut() += 1;
+        }
+    }
+
+    #[test]
+    fn test_03_dropping_the_cache_drops_every_resident_value() {
+        let dropped = Rc::new(RefCell::new(0));
+        {
+            let mut cache: LFUCache<i32, DropCounter> = LFUCache::new(5);
+            for i in 0..5 {
+                cache.set(i, DropCounter(dropped.clone()));
+            }
+            // bump some frequencies so entries span multiple FreqList buckets
+            cache.get(0, |_| {});
+            cache.get(1, |_| {});
+            cache.get(1, |_| {});
+        }
+
+        assert_eq!(*dropped.borrow(), 5);
+    }
+}
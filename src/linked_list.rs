@@ -51,3 +51,188 @@ mod tests {
         assert_eq!(list.pop(), None);
     }
 }
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+struct DoublyNode<T> {
+    data: T,
+    next: Option<Rc<RefCell<DoublyNode<T>>>>,
+    prev: Option<Weak<RefCell<DoublyNode<T>>>>,
+}
+
+/// 双向链表，头尾两端的插入、删除均为 O(1)
+///
+/// 前向链接使用 `Rc`，回指使用 `Weak`，避免首尾互指造成引用环导致内存泄漏。
+pub struct DoublyLinkedList<T> {
+    head: Option<Rc<RefCell<DoublyNode<T>>>>,
+    tail: Option<Rc<RefCell<DoublyNode<T>>>>,
+    len: usize,
+}
+
+impl<T> DoublyLinkedList<T> {
+    /// 创建新的双向链表
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    /// 返回链表中元素的数量
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 在链表头部插入新元素，时间复杂度 O(1)
+    pub fn push_front(&mut self, data: T) {
+        let node = Rc::new(RefCell::new(DoublyNode {
+            data,
+            next: self.head.clone(),
+            prev: None,
+        }));
+
+        match self.head.take() {
+            Some(old_head) => {
+                old_head.borrow_mut().prev = Some(Rc::downgrade(&node));
+                self.head = Some(node);
+            }
+            None => {
+                self.tail = Some(node.clone());
+                self.head = Some(node);
+            }
+        }
+        self.len += 1;
+    }
+
+    /// 在链表尾部插入新元素，时间复杂度 O(1)
+    pub fn push_back(&mut self, data: T) {
+        let node = Rc::new(RefCell::new(DoublyNode {
+            data,
+            next: None,
+            prev: self.tail.as_ref().map(Rc::downgrade),
+        }));
+
+        match self.tail.take() {
+            Some(old_tail) => {
+                old_tail.borrow_mut().next = Some(node.clone());
+                self.tail = Some(node);
+            }
+            None => {
+                self.head = Some(node.clone());
+                self.tail = Some(node);
+            }
+        }
+        self.len += 1;
+    }
+
+    /// 删除并返回链表头部元素，时间复杂度 O(1)
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.take().map(|old_head| {
+            match old_head.borrow_mut().next.take() {
+                Some(new_head) => {
+                    new_head.borrow_mut().prev = None;
+                    self.head = Some(new_head);
+                }
+                None => {
+                    self.tail = None;
+                }
+            }
+            self.len -= 1;
+
+            Rc::try_unwrap(old_head)
+                .ok()
+                .expect("node should have no other references")
+                .into_inner()
+                .data
+        })
+    }
+
+    /// 删除并返回链表尾部元素，时间复杂度 O(1)
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.take().map(|old_tail| {
+            match old_tail.borrow_mut().prev.take().and_then(|p| p.upgrade()) {
+                Some(new_tail) => {
+                    new_tail.borrow_mut().next = None;
+                    self.tail = Some(new_tail);
+                }
+                None => {
+                    self.head = None;
+                }
+            }
+            self.len -= 1;
+
+            Rc::try_unwrap(old_tail)
+                .ok()
+                .expect("node should have no other references")
+                .into_inner()
+                .data
+        })
+    }
+
+    /// 从头到尾遍历链表中的每个元素
+    pub fn iter<F: FnMut(&T)>(&self, mut f: F) {
+        let mut cur = self.head.clone();
+        while let Some(node) = cur {
+            f(&node.borrow().data);
+            cur = node.borrow().next.clone();
+        }
+    }
+
+    /// 从头到尾遍历并允许修改链表中的每个元素
+    pub fn iter_mut<F: FnMut(&mut T)>(&mut self, mut f: F) {
+        let mut cur = self.head.clone();
+        while let Some(node) = cur {
+            f(&mut node.borrow_mut().data);
+            cur = node.borrow().next.clone();
+        }
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod doubly_linked_list_tests {
+    use super::*;
+
+    #[test]
+    fn push_and_pop_both_ends() {
+        let mut list = DoublyLinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_front(0);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_front(), Some(0));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+    }
+
+    #[test]
+    fn iterates_front_to_back() {
+        let mut list = DoublyLinkedList::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        let mut seen = Vec::new();
+        list.iter(|v| seen.push(*v));
+        assert_eq!(seen, vec![1, 2, 3]);
+
+        list.iter_mut(|v| *v *= 10);
+        seen.clear();
+        list.iter(|v| seen.push(*v));
+        assert_eq!(seen, vec![10, 20, 30]);
+    }
+}
@@ -1,10 +1,16 @@
 use std::cell::{Ref, RefCell};
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const INITIAL_BUCKET_COUNT: usize = 16;
+const MAX_LOAD_FACTOR: f64 = 0.75;
 
 struct Node<K, V> {
     key: K,
     val: V,
+    expires_at: Option<Instant>,
     list_prev: Option<Rc<RefCell<Node<K, V>>>>,
     list_next: Option<Rc<RefCell<Node<K, V>>>>,
     hash_prev: Option<Rc<RefCell<Node<K, V>>>>,
@@ -12,10 +18,11 @@ struct Node<K, V> {
 }
 
 impl<K, V> Node<K, V> {
-    fn new(key: K, val: V) -> Self {
+    fn new(key: K, val: V, expires_at: Option<Instant>) -> Self {
         Self {
             key,
             val,
+            expires_at,
             list_prev: None,
             list_next: None,
             hash_prev: None,
@@ -24,22 +31,39 @@ impl<K, V> Node<K, V> {
     }
 }
 
-pub struct LRUCache<K, V, const H: usize = 100> {
+pub struct LRUCache<K, V, S = RandomState> {
     max_size: usize,
     total_size: usize,
+    default_ttl: Option<Duration>,
     list_header: Option<Rc<RefCell<Node<K, V>>>>,
     list_tail: Option<Rc<RefCell<Node<K, V>>>>,
-    hash_indices: Box<[Option<Rc<RefCell<Node<K, V>>>>; H]>,
+    hash_indices: Vec<Option<Rc<RefCell<Node<K, V>>>>>,
+    hasher: S,
 }
 
-impl<K: Hash + PartialEq<K>, V, const H: usize> LRUCache<K, V, H> {
+impl<K: Hash + PartialEq<K>, V> LRUCache<K, V, RandomState> {
     pub fn new(max_size: usize) -> Self {
+        Self::with_hasher(max_size, RandomState::new())
+    }
+
+    /// 创建一个所有条目默认在 `ttl` 后过期的缓存
+    pub fn with_ttl(max_size: usize, ttl: Duration) -> Self {
+        let mut cache = Self::new(max_size);
+        cache.default_ttl = Some(ttl);
+        cache
+    }
+}
+
+impl<K: Hash + PartialEq<K>, V, S: BuildHasher> LRUCache<K, V, S> {
+    pub fn with_hasher(max_size: usize, hasher: S) -> Self {
         Self {
             max_size,
             total_size: 0,
+            default_ttl: None,
             list_header: None,
             list_tail: None,
-            hash_indices: Box::new([const { None }; H]),
+            hash_indices: (0..INITIAL_BUCKET_COUNT).map(|_| None).collect(),
+            hasher,
         }
     }
 
@@ -66,36 +90,166 @@ impl<K: Hash + PartialEq<K>, V, const H: usize> LRUCache<K, V, H> {
         }
     }
 
+    /// 查找 `key` 对应的值但不更新其访问顺序
+    pub fn peek<T, F>(&mut self, key: T, then: F)
+    where
+        T: std::borrow::Borrow<K>,
+        F: Fn(&V),
+    {
+        if let Some(node) = self.find_node_by_key(key.borrow()) {
+            then(&node.borrow().val)
+        }
+    }
+
+    /// 查找 `key` 对应的值但不更新其访问顺序
+    pub fn try_peek<T: std::borrow::Borrow<K>>(&mut self, key: T) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.find_node_by_key(key.borrow())
+            .map(|node| node.borrow().val.clone())
+    }
+
+    /// 移除并返回最近最少使用的条目，途中遇到的已过期条目会被一并清理并跳过
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        loop {
+            let list_tail = self.list_tail.clone()?;
+            if self.is_expired(&list_tail) {
+                self.remove_node(list_tail);
+                continue;
+            }
+
+            self.remove_node(list_tail.clone());
+            let node = Rc::try_unwrap(list_tail)
+                .ok()
+                .expect("node should have no other references")
+                .into_inner();
+            return Some((node.key, node.val));
+        }
+    }
+
+    /// 从最近使用到最近最少使用遍历缓存中的每一项，已过期的条目会被一并清理并跳过
+    pub fn iter<F: FnMut(&K, &V)>(&mut self, mut f: F) {
+        let mut p = self.list_header.clone();
+        while let Some(node) = p {
+            p = node.borrow().list_next.clone();
+            if self.is_expired(&node) {
+                self.remove_node(node);
+                continue;
+            }
+            f(&node.borrow().key, &node.borrow().val);
+        }
+    }
+
     pub fn set(&mut self, key: K, val: V) {
+        let ttl = self.default_ttl;
+        self.set_with_expiry(key, val, ttl);
+    }
+
+    /// 插入或更新一个条目，并使用 `ttl` 覆盖缓存的默认过期时间
+    pub fn set_with_ttl(&mut self, key: K, val: V, ttl: Duration) {
+        self.set_with_expiry(key, val, Some(ttl));
+    }
+
+    fn set_with_expiry(&mut self, key: K, val: V, ttl: Option<Duration>) {
+        let expires_at = ttl.map(|ttl| Instant::now() + ttl);
+
         if let Some(node) = self.find_node_by_key(&key) {
             self.move_node_to_list_head(node.clone());
             node.borrow_mut().val = val;
+            node.borrow_mut().expires_at = expires_at;
         } else {
-            let node = Rc::new(RefCell::new(Node::new(key, val)));
+            let node = Rc::new(RefCell::new(Node::new(key, val, expires_at)));
 
             if let Some(list_header) = self.list_header.clone() {
                 list_header.borrow_mut().list_prev = Some(node.clone());
             }
             node.borrow_mut().list_next = self.list_header.clone();
             self.list_header = Some(node.clone());
-
-            let key_hash = Self::get_key_hash(&node.borrow().key);
-            if let Some(Some(index)) = self.hash_indices.get(key_hash) {
-                index.borrow_mut().hash_prev = Some(node);
-                self.hash_indices[key_hash] = Some(index.clone());
-            } else {
-                self.hash_indices[key_hash] = Some(node.clone());
+            if self.list_tail.is_none() {
+                self.list_tail = Some(node.clone());
             }
 
+            self.insert_into_hash_table(node);
+
             if self.total_size >= self.max_size {
                 self.remove_list_tail();
             } else {
                 self.total_size += 1;
+                self.grow_if_needed();
+            }
+        }
+    }
+
+    /// 清理所有已过期的条目，便于主动回收内存。
+    ///
+    /// 条目按最近使用顺序排列，与各自的过期时间无关，因此必须完整扫描一遍
+    /// 链表，而不能在遇到第一个未过期的节点时就提前终止。
+    pub fn purge_expired(&mut self) {
+        let mut p = self.list_tail.clone();
+        while let Some(node) = p {
+            p = node.borrow().list_prev.clone();
+            if self.is_expired(&node) {
+                self.remove_node(node);
             }
         }
     }
 
+    fn is_expired(&self, node: &Rc<RefCell<Node<K, V>>>) -> bool {
+        match node.borrow().expires_at {
+            Some(expires_at) => Instant::now() >= expires_at,
+            None => false,
+        }
+    }
+
+    fn remove_node(&mut self, node: Rc<RefCell<Node<K, V>>>) {
+        let prev_node = node.borrow().list_prev.clone();
+        let next_node = node.borrow().list_next.clone();
+
+        if let Some(prev_node) = prev_node.as_ref() {
+            prev_node.borrow_mut().list_next = next_node.clone();
+        } else {
+            self.list_header = next_node.clone();
+        }
+
+        if let Some(next_node) = next_node.as_ref() {
+            next_node.borrow_mut().list_prev = prev_node.clone();
+        } else {
+            self.list_tail = prev_node.clone();
+        }
+
+        let hash_prev = node.borrow().hash_prev.clone();
+        let hash_next = node.borrow().hash_next.clone();
+        if let Some(hash_prev) = hash_prev.clone() {
+            hash_prev.borrow_mut().hash_next = hash_next.clone();
+        } else {
+            let key_hash = self.get_key_hash(&node.borrow().key);
+            self.hash_indices[key_hash] = hash_next.clone();
+        }
+
+        if let Some(hash_next) = hash_next {
+            hash_next.borrow_mut().hash_prev = hash_prev;
+        }
+
+        self.total_size -= 1;
+    }
+
+    fn insert_into_hash_table(&mut self, node: Rc<RefCell<Node<K, V>>>) {
+        let key_hash = self.get_key_hash(&node.borrow().key);
+        if let Some(index) = self.hash_indices[key_hash].clone() {
+            index.borrow_mut().hash_prev = Some(node.clone());
+            node.borrow_mut().hash_next = Some(index);
+        }
+        self.hash_indices[key_hash] = Some(node);
+    }
+
     fn move_node_to_list_head(&mut self, node: Rc<RefCell<Node<K, V>>>) {
+        if let Some(list_header) = self.list_header.as_ref() {
+            if Rc::ptr_eq(list_header, &node) {
+                return;
+            }
+        }
+
         let prev_node = node.borrow().list_prev.clone();
         let next_node = node.borrow().list_next.clone();
 
@@ -105,10 +259,15 @@ impl<K: Hash + PartialEq<K>, V, const H: usize> LRUCache<K, V, H> {
 
         if let Some(next_node) = next_node.as_ref() {
             next_node.borrow_mut().list_prev = prev_node.clone();
+        } else {
+            self.list_tail = prev_node.clone();
         }
 
         node.borrow_mut().list_prev = None;
         node.borrow_mut().list_next = self.list_header.clone();
+        if let Some(list_header) = self.list_header.as_ref() {
+            list_header.borrow_mut().list_prev = Some(node.clone());
+        }
         self.list_header = Some(node.clone());
     }
 
@@ -119,6 +278,7 @@ impl<K: Hash + PartialEq<K>, V, const H: usize> LRUCache<K, V, H> {
                 self.list_tail = Some(prev_node);
             } else {
                 self.list_tail = None;
+                self.list_header = None;
             }
 
             let prev_node = list_tail.borrow().hash_prev.clone();
@@ -126,7 +286,7 @@ impl<K: Hash + PartialEq<K>, V, const H: usize> LRUCache<K, V, H> {
             if let Some(prev_node) = prev_node.clone() {
                 prev_node.borrow_mut().hash_next = next_node.clone();
             } else {
-                let key_hash = Self::get_key_hash(&list_tail.borrow().key);
+                let key_hash = self.get_key_hash(&list_tail.borrow().key);
                 self.hash_indices[key_hash] = next_node.clone();
             }
 
@@ -136,26 +296,49 @@ impl<K: Hash + PartialEq<K>, V, const H: usize> LRUCache<K, V, H> {
         }
     }
 
-    fn find_node_by_key(&self, key: &K) -> Option<Rc<RefCell<Node<K, V>>>> {
-        let key_hash = Self::get_key_hash(key);
+    fn find_node_by_key(&mut self, key: &K) -> Option<Rc<RefCell<Node<K, V>>>> {
+        let key_hash = self.get_key_hash(key);
 
-        if let Some(index) = self.hash_indices.get(key_hash) {
-            let mut p = index.clone();
-            while let Some(ptr) = p {
-                if ptr.borrow().key == *key {
-                    return Some(ptr);
+        let mut p = self.hash_indices.get(key_hash).cloned().flatten();
+        while let Some(ptr) = p {
+            if ptr.borrow().key == *key {
+                if self.is_expired(&ptr) {
+                    self.remove_node(ptr);
+                    return None;
                 }
-                p = ptr.borrow().hash_next.clone();
+                return Some(ptr);
             }
+            p = ptr.borrow().hash_next.clone();
         }
 
         None
     }
 
-    fn get_key_hash(key: &K) -> usize {
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        hasher.finish() as usize % H
+    fn get_key_hash(&self, key: &K) -> usize {
+        self.hasher.hash_one(key) as usize % self.hash_indices.len()
+    }
+
+    /// 当链表平均长度（`total_size / bucket_count`）超过负载因子阈值时，
+    /// 将桶数量翻倍并重新散列所有节点，保持查找近似 O(1)。
+    fn grow_if_needed(&mut self) {
+        let load_factor = self.total_size as f64 / self.hash_indices.len() as f64;
+        if load_factor <= MAX_LOAD_FACTOR {
+            return;
+        }
+
+        let mut nodes = Vec::with_capacity(self.total_size);
+        let mut p = self.list_header.clone();
+        while let Some(node) = p {
+            p = node.borrow().list_next.clone();
+            node.borrow_mut().hash_prev = None;
+            node.borrow_mut().hash_next = None;
+            nodes.push(node);
+        }
+
+        self.hash_indices = (0..self.hash_indices.len() * 2).map(|_| None).collect();
+        for node in nodes {
+            self.insert_into_hash_table(node);
+        }
     }
 }
 
@@ -197,4 +380,104 @@ mod tests {
 
         println!("total_size: {:?}", cache.total_size);
     }
+
+    #[test]
+    fn test_03_resizes_past_initial_bucket_count() {
+        let mut cache: LRUCache<i32, i32> = LRUCache::new(1000);
+
+        for i in 0..200 {
+            cache.set(i, i * 2);
+        }
+
+        for i in 0..200 {
+            assert_eq!(cache.try_get(i), Some(i * 2));
+        }
+        assert!(cache.hash_indices.len() > INITIAL_BUCKET_COUNT);
+    }
+
+    #[test]
+    fn test_04_ttl_expires_lazily() {
+        let mut cache: LRUCache<i32, i32> = LRUCache::with_ttl(10, Duration::from_millis(10));
+
+        cache.set(1, 1);
+        cache.set_with_ttl(2, 2, Duration::from_secs(60));
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(cache.try_get(1), None);
+        assert_eq!(cache.try_get(2), Some(2));
+    }
+
+    #[test]
+    fn test_04b_purge_expired_scans_past_a_non_expiring_tail() {
+        let mut cache: LRUCache<i32, i32> = LRUCache::new(10);
+
+        cache.set(1, 1); // no TTL, sits at the tail and never expires
+        cache.set_with_ttl(2, 2, Duration::from_millis(5)); // closer to the head
+        std::thread::sleep(Duration::from_millis(20));
+
+        cache.purge_expired();
+
+        assert_eq!(cache.total_size, 1);
+        assert_eq!(cache.try_get(2), None);
+        assert_eq!(cache.try_get(1), Some(1));
+    }
+
+    #[test]
+    fn test_05_peek_does_not_affect_order() {
+        let mut cache: LRUCache<i32, i32> = LRUCache::new(2);
+
+        cache.set(1, 1);
+        cache.set(2, 2);
+        assert_eq!(cache.try_peek(1), Some(1));
+
+        cache.set(3, 3); // 1 is still LRU since peek didn't touch order
+        assert_eq!(cache.try_get(1), None);
+        assert_eq!(cache.try_get(2), Some(2));
+        assert_eq!(cache.try_get(3), Some(3));
+    }
+
+    #[test]
+    fn test_06_pop_lru_and_iter_order() {
+        let mut cache: LRUCache<i32, i32> = LRUCache::new(10);
+
+        cache.set(1, 1);
+        cache.set(2, 2);
+        cache.set(3, 3);
+        cache.get(1, |_| {}); // 1 becomes most-recently-used
+
+        let mut seen = Vec::new();
+        cache.iter(|k, v| seen.push((*k, *v)));
+        assert_eq!(seen, vec![(1, 1), (3, 3), (2, 2)]);
+
+        assert_eq!(cache.pop_lru(), Some((2, 2)));
+        assert_eq!(cache.pop_lru(), Some((3, 3)));
+        assert_eq!(cache.pop_lru(), Some((1, 1)));
+        assert_eq!(cache.pop_lru(), None);
+    }
+
+    #[test]
+    fn test_07_iter_skips_expired_entries() {
+        let mut cache: LRUCache<i32, i32> = LRUCache::new(10);
+
+        cache.set_with_ttl(1, 100, Duration::from_millis(5));
+        cache.set(2, 200);
+        std::thread::sleep(Duration::from_millis(20));
+
+        let mut seen = Vec::new();
+        cache.iter(|k, v| seen.push((*k, *v)));
+        assert_eq!(seen, vec![(2, 200)]);
+        assert_eq!(cache.total_size, 1); // the expired entry was purged while iterating
+    }
+
+    #[test]
+    fn test_08_pop_lru_skips_expired_entries() {
+        let mut cache: LRUCache<i32, i32> = LRUCache::new(10);
+
+        cache.set_with_ttl(1, 100, Duration::from_millis(5));
+        cache.set(2, 200);
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(cache.pop_lru(), Some((2, 200)));
+        assert_eq!(cache.pop_lru(), None);
+    }
 }